@@ -0,0 +1,153 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use crate::cvss;
+use anyhow::{self as ah};
+use serde::{Deserialize, Serialize};
+
+/// A coarse severity bucket derived from a CVSS v3 base score, using the
+/// standard ranges (0.0 none, 0.1-3.9 low, 4.0-6.9 medium, 7.0-8.9 high,
+/// 9.0-10.0 critical). `Unknown` covers advisories without a CVSS vector.
+///
+/// Declaration order doubles as severity order (`Unknown` sorts lowest), so
+/// known buckets compare with plain `>=`; `min_severity` gating goes through
+/// [`Severity::meets_threshold`], which lets `Unknown` pass any threshold.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Severity {
+    #[default]
+    Unknown,
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn from_cvss_vector(vector: Option<&str>) -> Self {
+        match vector.and_then(cvss::base_score) {
+            None => Severity::Unknown,
+            Some(s) if s <= 0.0 => Severity::None,
+            Some(s) if s < 4.0 => Severity::Low,
+            Some(s) if s < 7.0 => Severity::Medium,
+            Some(s) if s < 9.0 => Severity::High,
+            Some(_) => Severity::Critical,
+        }
+    }
+
+    pub fn parse(s: &str) -> ah::Result<Self> {
+        match s {
+            "unknown" => Ok(Severity::Unknown),
+            "none" => Ok(Severity::None),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            other => Err(ah::format_err!("Invalid severity '{other}'")),
+        }
+    }
+
+    /// Whether this severity should trigger `min_severity` gating. `Unknown`
+    /// (a CVSS-less advisory, which a large fraction of RustSec advisories
+    /// are) always meets the threshold rather than being compared by sort
+    /// order: raising `min_severity` above `unknown` to cut noise must not
+    /// silently hide every advisory that lacks a CVSS vector.
+    pub fn meets_threshold(self, min_severity: Self) -> bool {
+        self == Severity::Unknown || self >= min_severity
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Severity::Unknown => "unknown",
+            Severity::None => "none",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A single parsed advisory finding for one affected package.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Finding {
+    pub id: String,
+    pub package: String,
+    pub version: String,
+    pub title: String,
+    pub cvss: Option<String>,
+    pub severity: Severity,
+}
+
+/// Counts of the non-vulnerability warnings cargo-audit reports alongside
+/// vulnerabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct AdvisoryWarnings {
+    pub unmaintained: usize,
+    pub yanked: usize,
+    pub unsound: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_buckets_follow_standard_ranges() {
+        assert_eq!(
+            Severity::from_cvss_vector(Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N")),
+            Severity::None
+        );
+        assert_eq!(
+            Severity::from_cvss_vector(Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H")),
+            Severity::Critical
+        );
+        assert_eq!(Severity::from_cvss_vector(None), Severity::Unknown);
+    }
+
+    #[test]
+    fn severity_ordering_places_unknown_lowest() {
+        assert!(Severity::Unknown < Severity::None);
+        assert!(Severity::Low < Severity::Critical);
+    }
+
+    #[test]
+    fn parse_roundtrips_display() {
+        for s in [
+            Severity::Unknown,
+            Severity::None,
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical,
+        ] {
+            assert_eq!(Severity::parse(&s.to_string()).unwrap(), s);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unknown_string() {
+        assert!(Severity::parse("extreme").is_err());
+    }
+
+    #[test]
+    fn meets_threshold_always_passes_for_unknown() {
+        assert!(Severity::Unknown.meets_threshold(Severity::Low));
+        assert!(Severity::Unknown.meets_threshold(Severity::Critical));
+    }
+
+    #[test]
+    fn meets_threshold_compares_known_severities_by_order() {
+        assert!(!Severity::Low.meets_threshold(Severity::High));
+        assert!(Severity::High.meets_threshold(Severity::Low));
+        assert!(Severity::Medium.meets_threshold(Severity::Medium));
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -3,83 +3,192 @@
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
 use crate::{
+    advisory::{AdvisoryWarnings, Finding, Severity},
     config::Config,
-    report::{Report, ReportEntry},
+    report::{EntryKind, Report, ReportEntry},
 };
 use anyhow::{self as ah, format_err as err};
+use serde::Deserialize;
 use serde_json as json;
-use std::{path::PathBuf, process::Stdio};
+use std::{collections::HashMap, future::Future, path::PathBuf, pin::Pin, process::Stdio};
 use tokio::{fs::read_dir, process::Command};
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt as _;
 
-fn split_json_parts(input: &str, expected_nr_parts: usize) -> ah::Result<Vec<String>> {
-    let mut parts = Vec::with_capacity(expected_nr_parts);
-    let mut part = String::with_capacity(input.len());
-    let mut indent = 0_i32;
-    let mut in_string = false;
-    let mut escape = false;
-
-    for c in input.chars() {
-        if escape {
-            part.push(c);
-            escape = false;
-            continue;
-        }
+/// Shape of a single entry in cargo-audit's `vulnerabilities.list`.
+#[derive(Debug, Deserialize)]
+struct RawFinding {
+    advisory: RawAdvisory,
+    package: RawPackage,
+}
 
-        match c {
-            '\\' => {
-                if in_string {
-                    escape = true;
-                    part.push(c);
-                } else {
-                    part.push(c);
-                }
-            }
-            '"' => {
-                part.push(c);
-                in_string = !in_string;
-            }
-            '{' => {
-                if !in_string {
-                    indent += 1;
-                }
-                part.push(c);
-            }
-            '}' => {
-                part.push(c);
-                if !in_string {
-                    indent -= 1;
-                    if indent <= 0 {
-                        let ptrim = part.trim();
-                        if !ptrim.is_empty() {
-                            parts.push(ptrim.to_string());
-                        }
-                        part.clear();
-                        indent = 0;
-                    }
-                }
+#[derive(Debug, Deserialize)]
+struct RawAdvisory {
+    id: String,
+    title: String,
+    cvss: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPackage {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawVulnerabilities {
+    #[serde(default)]
+    list: Vec<RawFinding>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawAuditResult {
+    #[serde(default)]
+    vulnerabilities: RawVulnerabilities,
+    #[serde(default)]
+    warnings: HashMap<String, Vec<json::Value>>,
+}
+
+/// Parse a single cargo-audit JSON result into typed findings, warning
+/// counts, and the highest severity bucket seen among the findings.
+fn parse_advisories(audit_result: &json::Value) -> (Vec<Finding>, AdvisoryWarnings, Severity) {
+    // cargo-audit's JSON shape is considered stable, but tolerate a result
+    // that doesn't match it rather than failing the whole audit run.
+    let raw: RawAuditResult = json::from_value(audit_result.clone()).unwrap_or_default();
+
+    let findings: Vec<Finding> = raw
+        .vulnerabilities
+        .list
+        .into_iter()
+        .map(|f| {
+            let severity = Severity::from_cvss_vector(f.advisory.cvss.as_deref());
+            Finding {
+                id: f.advisory.id,
+                package: f.package.name,
+                version: f.package.version,
+                title: f.advisory.title,
+                cvss: f.advisory.cvss,
+                severity,
             }
-            _ => {
-                part.push(c);
+        })
+        .collect();
+
+    let max_severity = findings
+        .iter()
+        .map(|f| f.severity)
+        .max()
+        .unwrap_or(Severity::Unknown);
+
+    let warnings = AdvisoryWarnings {
+        unmaintained: raw.warnings.get("unmaintained").map_or(0, Vec::len),
+        yanked: raw.warnings.get("yanked").map_or(0, Vec::len),
+        unsound: raw.warnings.get("unsound").map_or(0, Vec::len),
+    };
+
+    (findings, warnings, max_severity)
+}
+
+/// Whether `findings` should mark an entry as vulnerable: cargo-audit found
+/// something *and* at least one individual finding meets `min_severity`.
+/// This is gated per-finding via [`Severity::meets_threshold`], not against
+/// the aggregate max severity — `max()` sorts `Unknown` lowest, so gating on
+/// it would let one low-severity finding mask an unrelated CVSS-less one.
+fn is_vulnerable(findings: &[Finding], min_severity: Severity, found: bool) -> bool {
+    found && findings.iter().any(|f| f.severity.meets_threshold(min_severity))
+}
+
+/// Parse a whitespace-separated stream of top-level JSON values, as produced
+/// by `cargo audit --format json` for each audited binary. Unlike a
+/// brace-counting tokenizer, this natively handles top-level arrays, bare
+/// scalars, and any comment-free whitespace the JSON text may contain.
+fn parse_json_stream(input: &str) -> ah::Result<Vec<json::Value>> {
+    json::Deserializer::from_str(input)
+        .into_iter::<json::Value>()
+        .enumerate()
+        .map(|(i, r)| {
+            r.map_err(|e| err!("Parse JSON value #{i} (line {}, column {}): {e}", e.line(), e.column()))
+        })
+        .collect()
+}
+
+/// Fetch/update the advisory database into `config.cargo_audit().db()`, if
+/// `update_db()` is enabled. Sync failures are folded into `report` as
+/// warning messages rather than aborting the audit run, since a stale (but
+/// present) database is still useful.
+///
+/// Called once per run (from `main::run_once`) rather than from
+/// [`audit_binaries`]/[`audit_lockfiles`] themselves, since both passes (and
+/// each of their retries) would otherwise re-sync the same `db` directory.
+pub(crate) async fn sync_advisory_db(config: &Config, report: &mut Report) {
+    let ca = config.cargo_audit();
+    if !ca.update_db() || ca.offline() {
+        return;
+    }
+    let Some(db_path) = ca.db() else {
+        report.add_message(
+            "WARNING: cargo_audit.update_db is enabled but no 'db' path is configured; \
+             skipping advisory database sync."
+                .to_string(),
+        );
+        return;
+    };
+
+    let git_dir_exists = tokio::fs::metadata(db_path.join(".git")).await.is_ok();
+    let out = if git_dir_exists {
+        Command::new("git")
+            .arg("-C")
+            .arg(db_path)
+            .args(["pull", "--ff-only"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+    } else {
+        if let Err(e) = tokio::fs::create_dir_all(db_path).await {
+            report.add_message(format!(
+                "WARNING: Failed to create advisory database directory '{}': {}; skipping sync.",
+                db_path.display(),
+                e
+            ));
+            return;
+        }
+        Command::new("git")
+            .arg("clone")
+            .args(["--depth", "1"])
+            .arg(ca.advisory_db_url())
+            .arg(db_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+    };
+
+    match out {
+        Ok(out) if out.status.success() => {
+            if config.cargo_audit().debug() {
+                report.add_message(format!(
+                    "Synced advisory database at '{}'.",
+                    db_path.display()
+                ));
             }
         }
+        Ok(out) => {
+            report.add_message(format!(
+                "WARNING: Failed to sync advisory database at '{}': {}",
+                db_path.display(),
+                String::from_utf8_lossy(&out.stderr).trim()
+            ));
+        }
+        Err(e) => {
+            report.add_message(format!(
+                "WARNING: Failed to run git to sync advisory database: {}",
+                e
+            ));
+        }
     }
-    if escape {
-        return Err(err!("Trailing backslash in JSON data."));
-    }
-    if in_string {
-        return Err(err!("Unterminated string in JSON data."));
-    }
-    if indent != 0 {
-        return Err(err!("Mismatched braces in JSON data (indent = {indent})."));
-    }
-    if !part.trim().is_empty() {
-        return Err(err!("Trailing garbage at end of JSON data."));
-    }
-
-    Ok(parts)
 }
 
 pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Report, Report> {
@@ -143,6 +252,11 @@ pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Re
     if bins.is_empty() {
         report.add_message("WARNING: No existing paths to audit; cargo-audit skipped.".to_string());
     } else {
+        let min_severity = config
+            .cargo_audit()
+            .min_severity()
+            .map_err(|e| report.fail(format!("Parse cargo_audit.min_severity: {e}")))?;
+
         // Execute cargo-audit
         let mut cmd = Command::new(config.cargo_audit().exe());
         let mut cmd = cmd
@@ -178,8 +292,8 @@ pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Re
                 report.add_message("cargo-audit exited due to signal".to_string());
             }
         }
-        let parts = split_json_parts(&stdout, bins.len())
-            .map_err(|e| report.fail(format!("Split cargo-audit JSON output: {}", e)))?;
+        let parts = parse_json_stream(&stdout)
+            .map_err(|e| report.fail(format!("Parse cargo-audit JSON output: {}", e)))?;
         if parts.len() != bins.len() {
             return Err(report.fail(format!(
                 "cargo-audit returned {} JSON object(s) but {} binary(ies) were audited",
@@ -187,9 +301,9 @@ pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Re
                 bins.len()
             )));
         }
-        for (path, json_part) in bins.iter().cloned().zip(parts.into_iter()) {
-            let audit_result: json::Value = json::from_str(json_part.trim())
-                .map_err(|e| report.fail(format!("Parse cargo-audit JSON output: {}", e)))?;
+        for (path, audit_result) in bins.iter().cloned().zip(parts.into_iter()) {
+            let json_part = json::to_string(&audit_result)
+                .map_err(|e| report.fail(format!("Re-serialize cargo-audit JSON output: {}", e)))?;
 
             let json_pretty = json::to_string_pretty(&audit_result)
                 .map_err(|e| report.fail(format!("Format cargo-audit JSON output: {}", e)))?;
@@ -199,16 +313,22 @@ pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Re
                 println!("{json_pretty}");
             }
 
-            let vulnerable = audit_result
+            let (findings, warnings, max_severity) = parse_advisories(&audit_result);
+            let found = audit_result
                 .pointer("/vulnerabilities/found")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
+            let vulnerable = is_vulnerable(&findings, min_severity, found);
 
             report.add(ReportEntry {
                 path,
                 vulnerable,
                 json: json_part,
                 json_pretty,
+                findings,
+                warnings,
+                max_severity,
+                kind: EntryKind::Binary,
             })
         }
 
@@ -223,6 +343,186 @@ pub async fn audit_binaries(config: &Config, paths: &[PathBuf]) -> ah::Result<Re
     Ok(report)
 }
 
+/// Directory entries skipped while walking for `Cargo.lock` files: hidden
+/// directories (e.g. `.git`) and Cargo's own `target` build output, neither
+/// of which hold a `Cargo.lock` worth auditing and both of which can be huge.
+fn skip_walk_dir(name: &std::ffi::OsStr) -> bool {
+    name == "target" || name.to_str().is_some_and(|s| s.starts_with('.'))
+}
+
+/// Recursively collect all `Cargo.lock` files under `path`. If `path` is
+/// itself a file named `Cargo.lock`, it is returned directly without
+/// descending any further.
+///
+/// Child directories are stat'd with `symlink_metadata` rather than
+/// `metadata`, so a symlinked directory is never followed: the binary-audit
+/// path deliberately doesn't recurse at all, and following symlinks here
+/// would let a symlink loop (or one pointing at an ancestor) recurse forever.
+fn find_lockfiles(path: PathBuf) -> Pin<Box<dyn Future<Output = ah::Result<Vec<PathBuf>>> + Send>> {
+    Box::pin(async move {
+        let meta = tokio::fs::metadata(&path).await?;
+        if meta.is_dir() {
+            let mut found = Vec::new();
+            let mut dir = read_dir(&path).await?;
+            while let Some(entry) = dir.next_entry().await? {
+                let child_meta = tokio::fs::symlink_metadata(entry.path()).await?;
+                if child_meta.is_dir() {
+                    if skip_walk_dir(&entry.file_name()) {
+                        continue;
+                    }
+                    found.extend(find_lockfiles(entry.path()).await?);
+                } else if entry.file_name() == "Cargo.lock" {
+                    found.push(entry.path());
+                }
+            }
+            Ok(found)
+        } else if path.file_name().is_some_and(|n| n == "Cargo.lock") {
+            Ok(vec![path])
+        } else {
+            Ok(Vec::new())
+        }
+    })
+}
+
+/// Audit `Cargo.lock` files found under `paths` (found directly or by
+/// walking the directory tree), running `cargo audit --file <lockfile>` on
+/// each and feeding the result through the same findings/severity pipeline
+/// as [`audit_binaries`]. Entries are tagged `EntryKind::Lockfile` so reports
+/// can tell binary and lockfile findings apart.
+pub async fn audit_lockfiles(config: &Config, paths: &[PathBuf]) -> ah::Result<Report, Report> {
+    let mut report = Report::new();
+
+    let mut lockfiles = Vec::new();
+    for p in paths {
+        match find_lockfiles(p.clone()).await {
+            Ok(found) => lockfiles.extend(found),
+            Err(e) => {
+                report.add_message(format!(
+                    "WARNING: Failed to search '{}' for Cargo.lock files: {}; skipped.",
+                    p.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    if lockfiles.is_empty() {
+        report
+            .add_message("WARNING: No Cargo.lock files found; lockfile audit skipped.".to_string());
+        return Ok(report);
+    }
+
+    let min_severity = config
+        .cargo_audit()
+        .min_severity()
+        .map_err(|e| report.fail(format!("Parse cargo_audit.min_severity: {e}")))?;
+
+    for lockfile in lockfiles {
+        let mut cmd = Command::new(config.cargo_audit().exe());
+        let mut cmd = cmd
+            .arg("audit")
+            .args(["--deny", "warnings"])
+            .args(["--format", "json"]);
+        if let Some(db_path) = &config.cargo_audit().db() {
+            cmd = cmd.arg("--db").arg(db_path)
+        }
+        cmd = cmd
+            .arg("--file")
+            .arg(&lockfile)
+            .env_remove("TERM")
+            .env_remove("COLORTERM")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let out = cmd.output().await.map_err(|e| {
+            report.fail(format!(
+                "Error executing cargo-audit for '{}' ({}): {}",
+                lockfile.display(),
+                config.cargo_audit().exe().display(),
+                e
+            ))
+        })?;
+
+        let stdout = String::from_utf8(out.stdout).map_err(|e| {
+            report.fail(format!(
+                "Parse cargo-audit stdout as UTF-8 for '{}': {}",
+                lockfile.display(),
+                e
+            ))
+        })?;
+        if config.cargo_audit().debug() {
+            if let Some(code) = out.status.code() {
+                report.add_message(format!(
+                    "cargo-audit exited with code {} for '{}'",
+                    code,
+                    lockfile.display()
+                ));
+            } else {
+                report.add_message(format!(
+                    "cargo-audit exited due to signal for '{}'",
+                    lockfile.display()
+                ));
+            }
+        }
+
+        let parts = parse_json_stream(&stdout).map_err(|e| {
+            report.fail(format!(
+                "Parse cargo-audit JSON output for '{}': {}",
+                lockfile.display(),
+                e
+            ))
+        })?;
+        if parts.len() != 1 {
+            return Err(report.fail(format!(
+                "cargo-audit returned {} JSON object(s) for '{}'; expected exactly 1",
+                parts.len(),
+                lockfile.display()
+            )));
+        }
+        let audit_result = parts.into_iter().next().unwrap();
+
+        let json_part = json::to_string(&audit_result)
+            .map_err(|e| report.fail(format!("Re-serialize cargo-audit JSON output: {}", e)))?;
+        let json_pretty = json::to_string_pretty(&audit_result)
+            .map_err(|e| report.fail(format!("Format cargo-audit JSON output: {}", e)))?;
+
+        if config.cargo_audit().debug() {
+            println!("\n\naudit result for {}:", lockfile.display());
+            println!("{json_pretty}");
+        }
+
+        let (findings, warnings, max_severity) = parse_advisories(&audit_result);
+        let found = audit_result
+            .pointer("/vulnerabilities/found")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let vulnerable = is_vulnerable(&findings, min_severity, found);
+
+        report.add(ReportEntry {
+            path: lockfile.clone(),
+            vulnerable,
+            json: json_part,
+            json_pretty,
+            findings,
+            warnings,
+            max_severity,
+            kind: EntryKind::Lockfile,
+        });
+
+        let stderr = String::from_utf8(out.stderr)
+            .map_err(|e| report.fail(format!("Parse cargo-audit stderr as UTF-8: {}", e)))?;
+        if !stderr.trim().is_empty() {
+            report.add_message(format!(
+                "cargo-audit stderr for '{}':\n{}",
+                lockfile.display(),
+                stderr
+            ));
+        }
+    }
+
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,9 +530,9 @@ mod tests {
     #[test]
     fn split_single_object() {
         let input = r#"  {"a":1}  "#;
-        let parts = split_json_parts(input, 0).expect("should split single object");
+        let parts = parse_json_stream(input).expect("should parse single object");
         assert_eq!(parts.len(), 1);
-        assert_eq!(parts[0], r#"{"a":1}"#);
+        assert_eq!(parts[0], json::json!({"a": 1}));
     }
 
     #[test]
@@ -240,55 +540,148 @@ mod tests {
         let input = r#"{"a":1}
 
 {"b":2}"#;
-        let parts = split_json_parts(input, 1).expect("should split two objects");
+        let parts = parse_json_stream(input).expect("should parse two objects");
         assert_eq!(parts.len(), 2);
-        assert_eq!(parts[0], r#"{"a":1}"#);
-        assert_eq!(parts[1], r#"{"b":2}"#);
+        assert_eq!(parts[0], json::json!({"a": 1}));
+        assert_eq!(parts[1], json::json!({"b": 2}));
     }
 
     #[test]
     fn braces_inside_string_dont_affect_split() {
         let input = r#"{"s":"}{"}{}"#;
-        let parts = split_json_parts(input, 10).expect("should split into two objects");
+        let parts = parse_json_stream(input).expect("should parse into two objects");
         assert_eq!(parts.len(), 2);
-        assert_eq!(parts[0], r#"{"s":"}{"}"#);
-        assert_eq!(parts[1], r#"{}"#);
+        assert_eq!(parts[0], json::json!({"s": "}{"}));
+        assert_eq!(parts[1], json::json!({}));
     }
 
     #[test]
     fn unterminated_string_error() {
         let input = r#"{"a":"b}"#;
-        let err = split_json_parts(input, 1).unwrap_err();
-        assert!(err.to_string().contains("Unterminated string"));
+        let err = parse_json_stream(input).unwrap_err();
+        assert!(err.to_string().contains("line"));
     }
 
     #[test]
     fn trailing_backslash_error() {
         let input = r#"{"a":"b\"#;
-        let err = split_json_parts(input, 1).unwrap_err();
-        assert!(err.to_string().contains("Trailing backslash"));
+        let err = parse_json_stream(input).unwrap_err();
+        assert!(err.to_string().contains("line"));
     }
 
     #[test]
     fn mismatched_braces_error() {
         let input = r#"{"#;
-        let err = split_json_parts(input, 1).unwrap_err();
-        assert!(err.to_string().contains("Mismatched braces"));
+        let err = parse_json_stream(input).unwrap_err();
+        assert!(err.to_string().contains("line"));
     }
 
     #[test]
     fn trailing_garbage_error() {
         let input = r#"{} garbage"#;
-        let err = split_json_parts(input, 1).unwrap_err();
-        assert!(err.to_string().contains("Trailing garbage"));
+        let err = parse_json_stream(input).unwrap_err();
+        assert!(err.to_string().contains("line"));
     }
 
     #[test]
     fn nested_objects() {
         let input = r#"  {"a":{"b":{"c":3},"arr":[{"x":1}]}}  "#;
-        let parts = split_json_parts(input, 1).expect("should handle nested objects");
+        let parts = parse_json_stream(input).expect("should handle nested objects");
         assert_eq!(parts.len(), 1);
-        assert_eq!(parts[0], r#"{"a":{"b":{"c":3},"arr":[{"x":1}]}}"#);
+        assert_eq!(parts[0], json::json!({"a":{"b":{"c":3},"arr":[{"x":1}]}}));
+    }
+
+    #[test]
+    fn top_level_array() {
+        let input = r#"[1, 2, 3] {"a": 1}"#;
+        let parts = parse_json_stream(input).expect("should handle a top-level array");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0], json::json!([1, 2, 3]));
+        assert_eq!(parts[1], json::json!({"a": 1}));
+    }
+
+    #[test]
+    fn top_level_numeric_scalar() {
+        let input = r#"42 "hello" true"#;
+        let parts = parse_json_stream(input).expect("should handle bare scalars");
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], json::json!(42));
+        assert_eq!(parts[1], json::json!("hello"));
+        assert_eq!(parts[2], json::json!(true));
+    }
+
+    #[test]
+    fn parse_advisories_extracts_findings_and_max_severity() {
+        let audit_result = json::json!({
+            "vulnerabilities": {
+                "found": true,
+                "list": [{
+                    "advisory": {
+                        "id": "RUSTSEC-2026-0001",
+                        "title": "Example vulnerability",
+                        "cvss": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
+                    },
+                    "package": { "name": "examplecrate", "version": "1.2.3" },
+                }],
+            },
+            "warnings": {
+                "unmaintained": [{}],
+                "yanked": [{}, {}],
+            },
+        });
+        let (findings, warnings, max_severity) = parse_advisories(&audit_result);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "RUSTSEC-2026-0001");
+        assert_eq!(findings[0].package, "examplecrate");
+        assert_eq!(max_severity, Severity::Critical);
+        assert_eq!(warnings.unmaintained, 1);
+        assert_eq!(warnings.yanked, 2);
+        assert_eq!(warnings.unsound, 0);
+    }
+
+    #[test]
+    fn skip_walk_dir_skips_hidden_and_target() {
+        assert!(skip_walk_dir(std::ffi::OsStr::new("target")));
+        assert!(skip_walk_dir(std::ffi::OsStr::new(".git")));
+        assert!(!skip_walk_dir(std::ffi::OsStr::new("src")));
+    }
+
+    #[test]
+    fn parse_advisories_handles_empty_result() {
+        let (findings, warnings, max_severity) = parse_advisories(&json::json!({}));
+        assert!(findings.is_empty());
+        assert_eq!(warnings, AdvisoryWarnings::default());
+        assert_eq!(max_severity, Severity::Unknown);
+    }
+
+    fn finding_with_severity(severity: Severity) -> Finding {
+        Finding {
+            id: "RUSTSEC-2026-0001".to_string(),
+            package: "examplecrate".to_string(),
+            version: "1.2.3".to_string(),
+            title: "Example vulnerability".to_string(),
+            cvss: None,
+            severity,
+        }
+    }
+
+    #[test]
+    fn is_vulnerable_gates_per_finding_not_on_aggregate_max() {
+        // A CVSS-less (Unknown) finding alongside a Low one: the aggregate
+        // max_severity is Low, which would fail a min_severity of High and
+        // hide the Unknown finding entirely if gated on the aggregate. Gated
+        // per-finding, the Unknown finding still meets any threshold.
+        let findings = [
+            finding_with_severity(Severity::Unknown),
+            finding_with_severity(Severity::Low),
+        ];
+        assert!(is_vulnerable(&findings, Severity::High, true));
+    }
+
+    #[test]
+    fn is_vulnerable_requires_found_flag() {
+        let findings = [finding_with_severity(Severity::Critical)];
+        assert!(!is_vulnerable(&findings, Severity::Low, false));
     }
 }
 
@@ -0,0 +1,129 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use std::collections::HashMap;
+
+/// Compute the CVSS v3.0/v3.1 base score from a CVSS vector string, e.g.
+/// `"CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"`.
+///
+/// Returns `None` if the vector is missing a required metric or is not a
+/// CVSS v3 vector.
+pub fn base_score(vector: &str) -> Option<f64> {
+    if !vector.starts_with("CVSS:3.") {
+        return None;
+    }
+
+    let metrics: HashMap<&str, &str> = vector
+        .split('/')
+        .filter_map(|part| part.split_once(':'))
+        .collect();
+
+    let av = match *metrics.get("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match *metrics.get("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let scope_changed = match *metrics.get("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+    let pr = match (*metrics.get("PR")?, scope_changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match *metrics.get("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+
+    let impact_metric = |v: &str| -> Option<f64> {
+        match v {
+            "H" => Some(0.56),
+            "L" => Some(0.22),
+            "N" => Some(0.0),
+            _ => None,
+        }
+    };
+    let c = impact_metric(*metrics.get("C")?)?;
+    let i = impact_metric(*metrics.get("I")?)?;
+    let a = impact_metric(*metrics.get("A")?)?;
+
+    let iss = 1.0 - ((1.0 - c) * (1.0 - i) * (1.0 - a));
+    let impact = if scope_changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powf(15.0)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+
+    let raw = if scope_changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+
+    Some(round_up(raw.min(10.0)))
+}
+
+/// The CVSS "Roundup" function: round a score up to one decimal place.
+fn round_up(value: f64) -> f64 {
+    let hundred_thousandths = (value * 100_000.0).round() as i64;
+    if hundred_thousandths % 10_000 == 0 {
+        hundred_thousandths as f64 / 100_000.0
+    } else {
+        ((hundred_thousandths / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn critical_vector() {
+        // Log4Shell-class: unauthenticated, network, no interaction, full impact.
+        let score = base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H").unwrap();
+        assert_eq!(score, 10.0);
+    }
+
+    #[test]
+    fn medium_vector() {
+        let score = base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:U/C:L/I:N/A:N").unwrap();
+        assert!((4.0..7.0).contains(&score), "score was {score}");
+    }
+
+    #[test]
+    fn no_impact_is_zero() {
+        let score = base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N").unwrap();
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn unsupported_version_is_none() {
+        assert_eq!(base_score("CVSS:2.0/AV:N/AC:L/Au:N/C:C/I:C/A:C"), None);
+    }
+
+    #[test]
+    fn missing_metric_is_none() {
+        assert_eq!(base_score("CVSS:3.1/AV:N/AC:L"), None);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
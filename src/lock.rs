@@ -0,0 +1,61 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use anyhow::{self as ah, Context as _};
+use fs2::FileExt as _;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+
+/// How often to re-attempt the lock while waiting out `timeout`.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An OS-level advisory lock (`flock`/`fcntl` on Unix, `LockFileEx` on
+/// Windows, via the `fs2` crate) held on a configurable lock-file path for
+/// the lifetime of one `periodic-audit` run. Keeps a slow audit launched by
+/// cron/systemd from overlapping with the next scheduled invocation.
+///
+/// The lock is released automatically when this guard is dropped, so it
+/// covers every exit path (normal return, early return, `?`-propagated
+/// error) without any explicit cleanup.
+pub struct RunLock {
+    _file: std::fs::File,
+}
+
+impl RunLock {
+    /// Attempt to acquire the exclusive run lock at `path`, creating the
+    /// lock file if it does not exist. Polls up to `timeout` if the lock is
+    /// already held by another run.
+    ///
+    /// Returns `Ok(None)` if `timeout` elapses while the lock is still held
+    /// elsewhere; the caller should treat that as "a previous run is still
+    /// in progress" and exit cleanly rather than auditing.
+    pub async fn acquire(path: &Path, timeout: Duration) -> ah::Result<Option<Self>> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Open lock file '{}'", path.display()))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock_exclusive() {
+                Ok(()) => return Ok(Some(Self { _file: file })),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        return Ok(None);
+                    }
+                    sleep(POLL_INTERVAL).await;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Lock file '{}'", path.display()))
+                }
+            }
+        }
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -4,22 +4,33 @@
 
 #![forbid(unsafe_code)]
 
-use crate::{audit::audit_binaries, config::Config, mail::send_report};
+use crate::{
+    audit::{audit_binaries, audit_lockfiles, sync_advisory_db},
+    config::Config,
+    lock::RunLock,
+    report::{command, file, sql, Report},
+    watcher::ConfigWatcher,
+};
 use anyhow::{self as ah, Context as _};
+use arc_swap::ArcSwap;
 use clap::Parser;
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{future::Future, path::PathBuf, sync::Arc, time::Duration};
 use tokio::{runtime, time::sleep};
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
-use crate::systemd::systemd_notify_ready;
+use crate::systemd::{spawn_watchdog_pinger, systemd_notify_ready, systemd_notify_status};
 
+mod advisory;
 mod audit;
 mod config;
-mod mail;
+mod cvss;
+mod lock;
+mod notify;
 mod report;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod systemd;
+mod watcher;
 
 #[derive(Parser, Debug, Clone)]
 struct Opts {
@@ -48,20 +59,19 @@ impl Opts {
     }
 }
 
-async fn async_main(opts: Arc<Opts>) -> ah::Result<()> {
-    // Load the configuration file.
-    let conf = Config::load(&opts.get_config()).await.context(format!(
-        "Load configuration file '{}'",
-        opts.get_config().display()
-    ))?;
-
-    // Run cargo-audit on the specified paths, retrying on failure.
+/// Run `audit` against `conf`'s watched paths, retrying on failure up to
+/// `cargo_audit.tries` times with exponential backoff.
+async fn run_with_retries<F, Fut>(conf: &Config, audit: F) -> Report
+where
+    F: Fn(&Config, &[PathBuf]) -> Fut,
+    Fut: Future<Output = ah::Result<Report, Report>>,
+{
     let mut tries = 0_u32;
-    let report = loop {
-        let report = match audit_binaries(&conf, &conf.watch.paths).await {
+    loop {
+        let report = match audit(conf, conf.watch().paths()).await {
             Ok(report) => {
                 if !report.failed() {
-                    break report;
+                    return report;
                 }
                 report
             }
@@ -72,8 +82,8 @@ async fn async_main(opts: Arc<Opts>) -> ah::Result<()> {
         };
 
         tries += 1;
-        if tries >= conf.cargo_audit.tries().min(30) {
-            break report; // Give up.
+        if tries >= conf.cargo_audit().tries().min(30) {
+            return report; // Give up.
         }
 
         eprintln!("One or more audits failed. Retrying...");
@@ -81,17 +91,128 @@ async fn async_main(opts: Arc<Opts>) -> ah::Result<()> {
         let mut dur = (1_u64 << (tries - 1)) * 2;
         dur = dur.min(120);
         sleep(Duration::from_secs(dur)).await;
-    };
+    }
+}
+
+/// Run a single audit pass against `conf`, retrying on failure, and dispatch
+/// the resulting report to all enabled report sinks.
+async fn run_once(conf: &Config) -> ah::Result<()> {
+    // Sync the advisory database once per run, before either audit pass, so
+    // audit_binaries and audit_lockfiles (and their retries) share a single
+    // sync instead of each re-syncing the same `db` directory.
+    let mut report = Report::new();
+    sync_advisory_db(conf, &mut report).await;
+
+    // Run cargo-audit against the binaries under the watched paths, retrying
+    // on failure.
+    report.merge(run_with_retries(conf, audit_binaries).await);
+
+    // Additionally audit any `Cargo.lock` files found under the watched
+    // paths, if enabled, and fold the result into the same report.
+    if conf.cargo_audit().audit_lockfiles() {
+        report.merge(run_with_retries(conf, audit_lockfiles).await);
+    }
 
-    // Send the report e-mail.
-    send_report(&conf, &report)
+    // Dispatch the report to all enabled sinks.
+    file::write_report(conf, &report)
+        .await
+        .context("Write report file")?;
+    command::run(conf, &report)
         .await
-        .context("Send report e-mail")?;
+        .context("Run report command")?;
+    sql::write_report(conf, &report)
+        .await
+        .context("Write report to SQL sink")?;
+    notify::dispatch(conf, &report)
+        .await
+        .context("Dispatch notifications")?;
+
+    Ok(())
+}
 
-    // Notify systemd that we are ready.
+async fn async_main(opts: Arc<Opts>) -> ah::Result<()> {
+    let conf_path = opts.get_config();
+
+    // Load the configuration file.
+    let initial = Config::load(&conf_path)
+        .await
+        .with_context(|| format!("Load configuration file '{}'", conf_path.display()))?;
+
+    // Acquire the cross-process run lock, if configured, before doing
+    // anything else. This keeps a slow run from overlapping with the next
+    // scheduled invocation (e.g. from cron/systemd).
+    let _run_lock = if let Some(lock_conf) = initial.lock() {
+        let timeout = lock_conf.timeout().context("Parse lock.timeout")?;
+        match RunLock::acquire(lock_conf.path(), timeout)
+            .await
+            .context("Acquire run lock")?
+        {
+            Some(run_lock) => Some(run_lock),
+            None => {
+                eprintln!(
+                    "A previous periodic-audit run still holds the lock at '{}'; exiting.",
+                    lock_conf.path().display()
+                );
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+
+    let config = Arc::new(ArcSwap::from_pointee(initial));
+
+    // Watch the configuration file for changes and hot-reload it.
+    let _watcher = ConfigWatcher::spawn(conf_path.clone(), Arc::clone(&config))
+        .context("Spawn configuration file watcher")?;
+
+    // Keep the systemd watchdog (if configured) pinged on its own short
+    // timer, independent of the (possibly 24h-long) audit schedule.
     #[cfg(any(target_os = "linux", target_os = "android"))]
-    if !opts.no_systemd {
-        systemd_notify_ready().context("Notify systemd ready")?;
+    let _watchdog_pinger = (!opts.no_systemd).then(spawn_watchdog_pinger).flatten();
+
+    let mut first_pass = true;
+    loop {
+        let conf = config.load_full();
+
+        // With a schedule configured, this is a long-running daemon: a
+        // transient sink failure (webhook POST non-2xx, SQL connection
+        // drop, SMTP hiccup) shouldn't permanently kill it, so log and
+        // retry on the next interval instead of propagating. Without a
+        // schedule this is a one-shot invocation, so a failure should still
+        // surface as a non-zero exit.
+        let interval = match conf.schedule() {
+            Some(schedule) => schedule.interval().context("Parse schedule.interval")?,
+            None => None,
+        };
+
+        match run_once(&conf).await {
+            Ok(()) => {
+                // Notify systemd that we are ready, once the first pass succeeded.
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                if first_pass && !opts.no_systemd {
+                    systemd_notify_ready().context("Notify systemd ready")?;
+                }
+                first_pass = false;
+            }
+            Err(e) if interval.is_some() => {
+                eprintln!("Error during periodic audit run: {e:#}");
+            }
+            Err(e) => return Err(e),
+        }
+
+        let Some(interval) = interval else {
+            // No schedule configured; run once and exit.
+            break;
+        };
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if !opts.no_systemd {
+            systemd_notify_status(&format!("Sleeping {interval:?} until next audit."))
+                .context("Notify systemd status")?;
+        }
+
+        sleep(interval).await;
     }
 
     Ok(())
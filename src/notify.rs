@@ -0,0 +1,84 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use crate::{
+    config::Config,
+    report::{mail, webhook, Report},
+};
+use anyhow::{self as ah, Context as _};
+
+/// A delivery channel for a completed audit `Report`.
+/// [`mail::MailNotifier`] (SMTP) and [`webhook::WebhookNotifier`] (HTTP
+/// POST) are the first-party implementations. `notify()` should no-op
+/// (return `Ok(())`) when the backend has nothing to do, matching the
+/// early-return convention used by the other report sinks.
+pub trait Notifier {
+    async fn notify(&self, report: &Report) -> ah::Result<()>;
+}
+
+/// Run every notifier enabled by `config.notifiers()` against `report`. An
+/// empty (or absent) `notifiers` list runs every notifier that has a
+/// present, non-disabled config section, preserving pre-existing behavior
+/// for configs written before the `notifiers` list existed.
+pub async fn dispatch(config: &Config, report: &Report) -> ah::Result<()> {
+    if is_enabled(config, "mail") {
+        mail::send_report(config, report)
+            .await
+            .context("Send report e-mail")?;
+    }
+    if is_enabled(config, "webhook") {
+        webhook::send_report(config, report)
+            .await
+            .context("Send report webhook")?;
+    }
+    Ok(())
+}
+
+fn is_enabled(config: &Config, name: &str) -> bool {
+    let notifiers = config.notifiers();
+    notifiers.is_empty() || notifiers.iter().any(|n| n == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_from_toml(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn is_enabled_defaults_to_true_when_notifiers_unset() {
+        let config = config_from_toml(
+            r#"
+[watch]
+paths = ["/foo"]
+
+[cargo_audit]
+exe = "/usr/bin/cargo-audit"
+            "#,
+        );
+        assert!(is_enabled(&config, "mail"));
+        assert!(is_enabled(&config, "webhook"));
+    }
+
+    #[test]
+    fn is_enabled_restricts_to_listed_notifiers() {
+        let config = config_from_toml(
+            r#"
+notifiers = ["webhook"]
+
+[watch]
+paths = ["/foo"]
+
+[cargo_audit]
+exe = "/usr/bin/cargo-audit"
+            "#,
+        );
+        assert!(!is_enabled(&config, "mail"));
+        assert!(is_enabled(&config, "webhook"));
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -2,22 +2,51 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
+use crate::advisory::{AdvisoryWarnings, Finding, Severity};
+use anyhow::{self as ah};
 use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json as json;
 use std::path::PathBuf;
 
 pub mod command;
 pub mod file;
 pub mod mail;
+pub mod sql;
+pub mod webhook;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+/// Distinguishes a `ReportEntry` produced by auditing a binary executable
+/// from one produced by auditing a `Cargo.lock` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub enum EntryKind {
+    #[default]
+    Binary,
+    Lockfile,
+}
+
+impl std::fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EntryKind::Binary => "binary",
+            EntryKind::Lockfile => "lockfile",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize)]
 pub struct ReportEntry {
     pub path: PathBuf,
     pub vulnerable: bool,
     pub json: String,
     pub json_pretty: String,
+    pub findings: Vec<Finding>,
+    pub warnings: AdvisoryWarnings,
+    pub max_severity: Severity,
+    pub kind: EntryKind,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Report {
     stamp: DateTime<Utc>,
     entries: Vec<ReportEntry>,
@@ -46,10 +75,24 @@ impl Report {
         self.messages.push(msg);
     }
 
+    /// Fold another report's entries, messages, and failure/vulnerability
+    /// flags into this one. Used to combine the binary and `Cargo.lock`
+    /// audit passes into a single report before dispatch.
+    pub fn merge(&mut self, other: Report) {
+        self.entries.extend(other.entries);
+        self.messages.extend(other.messages);
+        self.failed |= other.failed;
+        self.vulnerable |= other.vulnerable;
+    }
+
     pub fn entries(&self) -> &[ReportEntry] {
         &self.entries
     }
 
+    pub fn stamp(&self) -> DateTime<Utc> {
+        self.stamp
+    }
+
     pub fn messages(&self) -> &[String] {
         &self.messages
     }
@@ -68,6 +111,67 @@ impl Report {
     pub fn vulnerable(&self) -> bool {
         self.vulnerable
     }
+
+    /// Expand `{status}`, `{vuln_count}`, `{failed}`, `{date}`, `{host}` and
+    /// `{paths}` tokens against this report's current state. Shared by the
+    /// `mail` and `webhook` notification backends so their subject/payload
+    /// templates use the same token set.
+    pub fn render_template(&self, template: &str, host: &str) -> String {
+        let status = if self.failed() {
+            "FAILED"
+        } else if self.vulnerable() {
+            "VULNERABLE"
+        } else {
+            "OK"
+        };
+        let vuln_count = self.entries.iter().filter(|e| e.vulnerable).count();
+        let paths = self
+            .entries
+            .iter()
+            .map(|e| e.path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        template
+            .replace("{status}", status)
+            .replace("{vuln_count}", &vuln_count.to_string())
+            .replace("{failed}", &self.failed().to_string())
+            .replace("{date}", &self.stamp.format("%+").to_string())
+            .replace("{host}", host)
+            .replace("{paths}", &paths)
+    }
+
+    /// Render the full report as a single pretty-printed JSON object.
+    pub fn to_json(&self) -> ah::Result<String> {
+        Ok(json::to_string_pretty(self)?)
+    }
+
+    /// Render the report as newline-delimited JSON: one object per entry,
+    /// followed by one summary object carrying `stamp`/`failed`/`vulnerable`/
+    /// `messages`. Suited for piping into log shippers or `jq`.
+    pub fn to_ndjson(&self) -> ah::Result<String> {
+        #[derive(Serialize)]
+        struct NdjsonSummary<'a> {
+            stamp: DateTime<Utc>,
+            failed: bool,
+            vulnerable: bool,
+            messages: &'a [String],
+        }
+
+        let mut out = String::with_capacity(4096);
+        for entry in &self.entries {
+            out.push_str(&json::to_string(entry)?);
+            out.push('\n');
+        }
+        out.push_str(&json::to_string(&NdjsonSummary {
+            stamp: self.stamp,
+            failed: self.failed,
+            vulnerable: self.vulnerable,
+            messages: &self.messages,
+        })?);
+        out.push('\n');
+        Ok(out)
+    }
 }
 
 impl std::fmt::Display for Report {
@@ -82,8 +186,9 @@ impl std::fmt::Display for Report {
             for entry in self.entries() {
                 writeln!(
                     f,
-                    "  {}: {}",
+                    "  {} [{}]: {}",
                     entry.path.display(),
+                    entry.kind,
                     if entry.vulnerable { "VULNERABLE" } else { "Ok" }
                 )?;
             }
@@ -98,11 +203,101 @@ impl std::fmt::Display for Report {
         // Vulnerability details
         if !self.failed() {
             for entry in self.entries().iter().filter(|e| e.vulnerable) {
-                writeln!(f, "\n\n{}:\n{}", entry.path.display(), entry.json_pretty)?;
+                writeln!(
+                    f,
+                    "\n\n{} [{}] (max severity: {}):",
+                    entry.path.display(),
+                    entry.kind,
+                    entry.max_severity
+                )?;
+                for finding in &entry.findings {
+                    writeln!(
+                        f,
+                        "  [{}] {} {}: {} (severity: {})",
+                        finding.id, finding.package, finding.version, finding.title, finding.severity
+                    )?;
+                }
+                writeln!(f, "{}", entry.json_pretty)?;
             }
         }
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_round_trips_through_serde() {
+        let mut report = Report::new();
+        report.add(ReportEntry {
+            path: "/bin/foo".into(),
+            vulnerable: true,
+            json: "{}".to_string(),
+            json_pretty: "{}".to_string(),
+            ..Default::default()
+        });
+        let s = report.to_json().unwrap();
+        let parsed: json::Value = json::from_str(&s).unwrap();
+        assert_eq!(parsed["vulnerable"], true);
+        assert_eq!(parsed["entries"][0]["path"], "/bin/foo");
+    }
+
+    #[test]
+    fn to_ndjson_emits_one_line_per_entry_plus_summary() {
+        let mut report = Report::new();
+        report.add(ReportEntry {
+            path: "/bin/foo".into(),
+            ..Default::default()
+        });
+        report.add(ReportEntry {
+            path: "/bin/bar".into(),
+            ..Default::default()
+        });
+        let s = report.to_ndjson().unwrap();
+        assert_eq!(s.lines().count(), 3);
+        let summary: json::Value = json::from_str(s.lines().last().unwrap()).unwrap();
+        assert_eq!(summary["failed"], false);
+    }
+
+    #[test]
+    fn render_template_expands_tokens() {
+        let mut report = Report::new();
+        report.add(ReportEntry {
+            path: "/bin/foo".into(),
+            vulnerable: true,
+            ..Default::default()
+        });
+        let rendered = report.render_template("{status}: {vuln_count} on {host}", "myhost");
+        assert_eq!(rendered, "VULNERABLE: 1 on myhost");
+    }
+
+    #[test]
+    fn merge_combines_entries_and_flags() {
+        let mut report = Report::new();
+        report.add(ReportEntry {
+            path: "/bin/foo".into(),
+            kind: EntryKind::Binary,
+            ..Default::default()
+        });
+
+        let mut lockfile_report = Report::new();
+        lockfile_report.add(ReportEntry {
+            path: "/src/Cargo.lock".into(),
+            vulnerable: true,
+            kind: EntryKind::Lockfile,
+            ..Default::default()
+        });
+        lockfile_report.add_message("WARNING: something".to_string());
+
+        report.merge(lockfile_report);
+
+        assert_eq!(report.entries().len(), 2);
+        assert_eq!(report.entries()[1].kind, EntryKind::Lockfile);
+        assert!(report.vulnerable());
+        assert_eq!(report.messages().len(), 1);
+    }
+}
+
 // vim: ts=4 sw=4 expandtab
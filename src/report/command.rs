@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
-use crate::{config::Config, report::Report};
+use crate::{
+    config::{Config, ReportFormat},
+    report::Report,
+};
 use anyhow::{self as ah, Context as _};
 use std::process::Stdio;
 use tokio::{io::AsyncWriteExt as _, process::Command};
@@ -15,6 +18,12 @@ pub async fn run(config: &Config, report: &Report) -> ah::Result<()> {
         return Ok(());
     }
 
+    let s = match rc.format().context("Parse report_command.format")? {
+        ReportFormat::Text => format!("{report}"),
+        ReportFormat::Json => report.to_json().context("Render report as JSON")?,
+        ReportFormat::Ndjson => report.to_ndjson().context("Render report as NDJSON")?,
+    };
+
     let mut child = Command::new(rc.exe())
         .stdin(Stdio::piped())
         .stdout(Stdio::inherit())
@@ -24,7 +33,7 @@ pub async fn run(config: &Config, report: &Report) -> ah::Result<()> {
 
     let mut stdin = child.stdin.take().context("Open report command stdin")?;
     stdin
-        .write_all(format!("{report}").as_bytes())
+        .write_all(s.as_bytes())
         .await
         .context("Write report to report-command stdin")?;
 
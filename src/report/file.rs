@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
-use crate::{config::Config, report::Report};
+use crate::{
+    config::{Config, ReportFormat},
+    report::Report,
+};
 use anyhow::{self as ah, Context as _};
 use tokio::{fs::OpenOptions, io::AsyncWriteExt as _};
 
@@ -26,7 +29,13 @@ pub async fn write_report(config: &Config, report: &Report) -> ah::Result<()> {
         .await
         .with_context(|| format!("Open report file '{}'", rf.path().display()))?;
 
-    let s = format!("{report}\n\n\n==========================================================\n\n");
+    let s = match rf.format().context("Parse report_file.format")? {
+        ReportFormat::Text => {
+            format!("{report}\n\n\n==========================================================\n\n")
+        }
+        ReportFormat::Json => format!("{}\n", report.to_json().context("Render report as JSON")?),
+        ReportFormat::Ndjson => report.to_ndjson().context("Render report as NDJSON")?,
+    };
 
     file.write_all(s.as_bytes())
         .await
@@ -2,25 +2,248 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
-use crate::{config::Config, report::Report};
+use crate::{
+    config::{Config, ConfigReportMail, MailBodyFormat, MailTls},
+    notify::Notifier as _,
+    report::Report,
+};
 use anyhow::{self as ah, Context as _};
 use lettre::{
     AsyncSmtpTransport, AsyncTransport as _, Message, Tokio1Executor,
-    message::{Mailbox, header::ContentType},
+    message::{header::ContentType, Attachment, Mailbox, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
 };
 use std::sync::Arc;
 use tokio::{sync::Semaphore, task::JoinSet};
 
+/// Split a `relay` string of the form `[smtp[s]://]host[:port]` into its
+/// host and optional port, ignoring the scheme: the TLS policy is now
+/// controlled explicitly via `mail.tls` instead of being inferred from it.
+fn split_host_port(relay: &str) -> (&str, Option<u16>) {
+    let without_scheme = relay.split("://").next_back().unwrap_or(relay);
+    match without_scheme.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host, Some(port)),
+            Err(_) => (without_scheme, None),
+        },
+        None => (without_scheme, None),
+    }
+}
+
+/// Resolve the recipient list for `report` by evaluating `rm`'s routing
+/// rules in order and taking the first match's `to` list; falls back to the
+/// unconditional `rm.to()` when no rule matched (or none are configured).
+fn route_recipients<'a>(rm: &'a ConfigReportMail, report: &Report) -> &'a [String] {
+    for route in rm.routes() {
+        let matches = match route.when() {
+            "failed" => report.failed(),
+            "vulnerable" => !report.failed() && report.vulnerable(),
+            "clean" => !report.failed() && !report.vulnerable(),
+            "always" => true,
+            _ => false,
+        };
+        if matches {
+            return route.to();
+        }
+    }
+    rm.to()
+}
+
+/// Escape the characters HTML treats specially in text content.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render `report` as a standalone HTML document, highlighting vulnerable
+/// entries, their per-finding severity, and linking each advisory ID to its
+/// RustSec page.
+fn render_html(report: &Report) -> String {
+    let status = if report.failed() {
+        "FAILED"
+    } else if report.vulnerable() {
+        "VULNERABLE"
+    } else {
+        "OK"
+    };
+
+    let mut html = String::with_capacity(4096);
+    html.push_str("<html><body>\n");
+    html.push_str(&format!("<h1>periodic-audit report: {status}</h1>\n"));
+
+    if report.failed() {
+        html.push_str("<p><strong>The audit run failed.</strong></p>\n");
+    }
+
+    html.push_str("<table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\n");
+    html.push_str("<tr><th>Path</th><th>Kind</th><th>Result</th><th>Max severity</th></tr>\n");
+    for entry in report.entries() {
+        let (color, result) = if entry.vulnerable {
+            ("red", "VULNERABLE")
+        } else {
+            ("green", "Ok")
+        };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td style=\"color:{color}\">{result}</td><td>{}</td></tr>\n",
+            escape_html(&entry.path.display().to_string()),
+            entry.kind,
+            entry.max_severity,
+        ));
+    }
+    html.push_str("</table>\n");
+
+    for entry in report.entries().iter().filter(|e| e.vulnerable) {
+        html.push_str(&format!(
+            "<h2>{}</h2>\n",
+            escape_html(&entry.path.display().to_string())
+        ));
+        html.push_str("<table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\n");
+        html.push_str("<tr><th>Advisory</th><th>Package</th><th>Version</th><th>Title</th><th>Severity</th></tr>\n");
+        for finding in &entry.findings {
+            html.push_str(&format!(
+                "<tr><td><a href=\"https://rustsec.org/advisories/{id}.html\">{id}</a></td><td>{package}</td><td>{version}</td><td>{title}</td><td>{severity}</td></tr>\n",
+                id = escape_html(&finding.id),
+                package = escape_html(&finding.package),
+                version = escape_html(&finding.version),
+                title = escape_html(&finding.title),
+                severity = finding.severity,
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    for msg in report.messages() {
+        html.push_str(&format!("<pre>{}</pre>\n", escape_html(msg)));
+    }
+
+    html.push_str("</body></html>\n");
+    html
+}
+
+/// The rendered body of a report e-mail. A single `text/plain`/`text/html`
+/// part is used whenever possible, to preserve the original single-part mail
+/// shape; a message only escalates to `Multi` when `body_format = "both"` or
+/// `attach_json` is enabled (even if the report has no entries to attach).
+enum MailBody {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+/// Build the message body according to `rm.body_format()`, optionally
+/// wrapped in a `multipart/mixed` with one `application/json` attachment
+/// per report entry when `rm.attach_json()` is enabled. The common case —
+/// `body_format = "plain"` with no attachment — stays a plain single part
+/// rather than being wrapped in `multipart/mixed`.
+fn build_body(rm: &ConfigReportMail, report: &Report) -> ah::Result<MailBody> {
+    if !rm.attach_json() {
+        return Ok(match rm.body_format()? {
+            MailBodyFormat::Plain => MailBody::Single(SinglePart::plain(format!("{report}"))),
+            MailBodyFormat::Html => MailBody::Single(SinglePart::html(render_html(report))),
+            MailBodyFormat::Both => MailBody::Multi(
+                MultiPart::alternative()
+                    .singlepart(SinglePart::plain(format!("{report}")))
+                    .singlepart(SinglePart::html(render_html(report))),
+            ),
+        });
+    }
+
+    let content = match rm.body_format()? {
+        MailBodyFormat::Plain => {
+            MultiPart::mixed().singlepart(SinglePart::plain(format!("{report}")))
+        }
+        MailBodyFormat::Html => {
+            MultiPart::mixed().singlepart(SinglePart::html(render_html(report)))
+        }
+        MailBodyFormat::Both => MultiPart::mixed().multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::plain(format!("{report}")))
+                .singlepart(SinglePart::html(render_html(report))),
+        ),
+    };
+
+    let mut with_attachments = content;
+    for entry in report.entries() {
+        let filename = format!(
+            "{}.json",
+            entry
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "report".to_string())
+        );
+        with_attachments = with_attachments.singlepart(
+            Attachment::new(filename).body(entry.json_pretty.clone(), ContentType::parse("application/json")?),
+        );
+    }
+    Ok(MailBody::Multi(with_attachments))
+}
+
+async fn build_transport(rm: &ConfigReportMail) -> ah::Result<AsyncSmtpTransport<Tokio1Executor>> {
+    let Some(relay) = rm.relay() else {
+        return Ok(AsyncSmtpTransport::<Tokio1Executor>::unencrypted_localhost());
+    };
+
+    let (host, port) = split_host_port(relay);
+    let mut builder = match rm.tls()? {
+        MailTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+        MailTls::StartTls => {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).context("Build STARTTLS relay")?
+        }
+        MailTls::Wrapper => {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(host).context("Build TLS relay")?
+        }
+    };
+    if let Some(port) = port {
+        builder = builder.port(port);
+    }
+
+    if let Some(username) = rm.username() {
+        let password = rm.password().await?.unwrap_or_default();
+        builder = builder.credentials(Credentials::new(username.to_string(), password));
+    }
+
+    Ok(builder.build())
+}
+
+/// SMTP delivery of a `Report`, the first-party [`Notifier`] implementation
+/// alongside [`crate::report::webhook::WebhookNotifier`].
+pub struct MailNotifier(ConfigReportMail);
+
+impl MailNotifier {
+    pub fn new(rm: ConfigReportMail) -> Self {
+        Self(rm)
+    }
+}
+
+impl crate::notify::Notifier for MailNotifier {
+    async fn notify(&self, report: &Report) -> ah::Result<()> {
+        send_report_impl(&self.0, report).await
+    }
+}
+
 pub async fn send_report(config: &Config, report: &Report) -> ah::Result<()> {
-    if config.mail().disabled() {
+    let Some(rm) = config.report_mail() else {
+        return Ok(());
+    };
+    if rm.disabled() {
         println!("Mail sending is disabled; not sending report e-mail.");
         return Ok(());
     }
-    if config.mail().to().is_empty() {
-        println!("No mail.to addresses configured; not sending report e-mail.");
+    MailNotifier::new(rm.clone()).notify(report).await
+}
+
+async fn send_report_impl(rm: &ConfigReportMail, report: &Report) -> ah::Result<()> {
+    let to = route_recipients(rm, report);
+    if to.is_empty() {
+        println!("No report_mail recipients for this report; not sending report e-mail.");
         return Ok(());
     }
 
+    let host = hostname::get()
+        .map(|h| h.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "unknown".to_string());
     let subject = format!(
         "{}{}",
         if report.failed() {
@@ -30,35 +253,28 @@ pub async fn send_report(config: &Config, report: &Report) -> ah::Result<()> {
         } else {
             ""
         },
-        config.mail().subject(),
+        report.render_template(rm.subject(), &host),
     );
-    let from: Mailbox = config
-        .mail()
-        .from()
-        .parse()
-        .context("Parse mail.from address")?;
-    let report_string = format!("{report}");
+    let from: Mailbox = rm.from().parse().context("Parse report_mail.from address")?;
 
-    let mut messages = Vec::with_capacity(config.mail().to().len());
+    let mut messages = Vec::with_capacity(to.len());
 
-    for to in config.mail().to() {
-        let message = Message::builder()
+    for to in to {
+        let builder = Message::builder()
             .from(from.clone())
-            .to(to.parse().context("Parse mail.to address")?)
+            .to(to.parse().context("Parse report_mail.to address")?)
             .subject(&subject)
-            .user_agent("periodic-audit".to_string())
-            .header(ContentType::TEXT_PLAIN)
-            .body(report_string.clone())?;
+            .user_agent("periodic-audit".to_string());
+        let message = match build_body(rm, report).context("Build mail body")? {
+            MailBody::Single(part) => builder.singlepart(part)?,
+            MailBody::Multi(multi) => builder.multipart(multi)?,
+        };
         messages.push(message);
     }
 
-    let transport = if let Some(relay) = &config.mail().relay() {
-        Arc::new(AsyncSmtpTransport::<Tokio1Executor>::from_url(relay)?.build())
-    } else {
-        Arc::new(AsyncSmtpTransport::<Tokio1Executor>::unencrypted_localhost())
-    };
+    let transport = Arc::new(build_transport(rm).await.context("Build SMTP transport")?);
 
-    let sema = Arc::new(Semaphore::new(config.mail().max_concurrency()));
+    let sema = Arc::new(Semaphore::new(rm.max_concurrency()));
     let mut set = JoinSet::new();
     for message in messages {
         let transport = Arc::clone(&transport);
@@ -75,4 +291,152 @@ pub async fn send_report(config: &Config, report: &Report) -> ah::Result<()> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_host_port_strips_scheme() {
+        assert_eq!(
+            split_host_port("smtp://smtp.example.com:587"),
+            ("smtp.example.com", Some(587))
+        );
+    }
+
+    #[test]
+    fn split_host_port_bare_host() {
+        assert_eq!(split_host_port("smtp.example.com"), ("smtp.example.com", None));
+    }
+
+    #[test]
+    fn split_host_port_bare_host_with_port() {
+        assert_eq!(
+            split_host_port("smtp.example.com:465"),
+            ("smtp.example.com", Some(465))
+        );
+    }
+
+    fn vulnerable_report() -> Report {
+        let mut report = Report::new();
+        report.add(crate::report::ReportEntry {
+            path: "/bin/foo".into(),
+            vulnerable: true,
+            ..Default::default()
+        });
+        report
+    }
+
+    #[test]
+    fn route_recipients_matches_vulnerable_rule() {
+        let toml = r#"
+subject = "s"
+from = "noreply@example.com"
+to = ["default@example.com"]
+
+[[routes]]
+when = "vulnerable"
+to = ["security@example.com"]
+        "#;
+        let rm: ConfigReportMail = toml::from_str(toml).unwrap();
+        let report = vulnerable_report();
+        assert_eq!(
+            route_recipients(&rm, &report),
+            ["security@example.com".to_string()]
+        );
+    }
+
+    #[test]
+    fn render_html_escapes_and_highlights_vulnerable_entries() {
+        let mut report = Report::new();
+        report.add(crate::report::ReportEntry {
+            path: "/bin/<foo>".into(),
+            vulnerable: true,
+            findings: vec![crate::advisory::Finding {
+                id: "RUSTSEC-2026-0001".to_string(),
+                package: "examplecrate".to_string(),
+                version: "1.0.0".to_string(),
+                title: "Example".to_string(),
+                cvss: None,
+                severity: crate::advisory::Severity::High,
+            }],
+            ..Default::default()
+        });
+        let html = render_html(&report);
+        assert!(html.contains("&lt;foo&gt;"));
+        assert!(html.contains("VULNERABLE"));
+        assert!(html.contains("RUSTSEC-2026-0001"));
+        assert!(html.contains("rustsec.org/advisories/RUSTSEC-2026-0001.html"));
+    }
+
+    #[test]
+    fn build_body_attaches_json_when_enabled() {
+        let toml = r#"
+subject = "s"
+from = "noreply@example.com"
+to = ["one@example.com"]
+body_format = "both"
+attach_json = true
+        "#;
+        let rm: ConfigReportMail = toml::from_str(toml).unwrap();
+        let mut report = Report::new();
+        report.add(crate::report::ReportEntry {
+            path: "/bin/foo".into(),
+            json_pretty: "{}".to_string(),
+            ..Default::default()
+        });
+        let body = match build_body(&rm, &report).unwrap() {
+            MailBody::Multi(multi) => multi,
+            MailBody::Single(_) => panic!("expected a multipart body when attach_json is enabled"),
+        };
+        let message = Message::builder()
+            .from("noreply@example.com".parse().unwrap())
+            .to("one@example.com".parse().unwrap())
+            .subject("s")
+            .multipart(body)
+            .unwrap();
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("application/json"));
+        assert!(raw.contains("foo.json"));
+    }
+
+    #[test]
+    fn build_body_stays_single_part_for_plain_without_attachment() {
+        let toml = r#"
+subject = "s"
+from = "noreply@example.com"
+to = ["one@example.com"]
+        "#;
+        let rm: ConfigReportMail = toml::from_str(toml).unwrap();
+        let report = Report::new();
+        let body = match build_body(&rm, &report).unwrap() {
+            MailBody::Single(part) => part,
+            MailBody::Multi(_) => panic!("expected a single-part plain body by default"),
+        };
+        let message = Message::builder()
+            .from("noreply@example.com".parse().unwrap())
+            .to("one@example.com".parse().unwrap())
+            .subject("s")
+            .singlepart(body)
+            .unwrap();
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("Content-Type: text/plain"));
+        assert!(!raw.contains("multipart/mixed"));
+    }
+
+    #[test]
+    fn route_recipients_falls_back_to_default_to() {
+        let toml = r#"
+subject = "s"
+from = "noreply@example.com"
+to = ["default@example.com"]
+        "#;
+        let rm: ConfigReportMail = toml::from_str(toml).unwrap();
+        let report = Report::new();
+        assert_eq!(
+            route_recipients(&rm, &report),
+            ["default@example.com".to_string()]
+        );
+    }
+}
+
 // vim: ts=4 sw=4 expandtab
@@ -0,0 +1,119 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use crate::{config::Config, report::Report};
+use anyhow::{self as ah, Context as _};
+use serde_json as json;
+use sqlx::postgres::PgPoolOptions;
+
+/// Validate `table` as a bare SQL identifier (`^[A-Za-z_][A-Za-z0-9_]*$`).
+/// The table name can't be bound as a query parameter and is interpolated
+/// directly into the DDL/DML below, so an arbitrary `report_sql.table`
+/// string must be rejected up front rather than trusted into the query text.
+fn validate_table_name(table: &str) -> ah::Result<()> {
+    let valid = table
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && table
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ah::format_err!(
+            "Invalid report_sql.table '{table}': must match ^[A-Za-z_][A-Za-z0-9_]*$"
+        ))
+    }
+}
+
+pub async fn write_report(config: &Config, report: &Report) -> ah::Result<()> {
+    let Some(rs) = config.report_sql() else {
+        return Ok(());
+    };
+    if rs.disabled() {
+        return Ok(());
+    }
+
+    let table = rs.table();
+    validate_table_name(table).context("Validate report_sql.table")?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(rs.url())
+        .await
+        .context("Connect to report_sql.url")?;
+
+    sqlx::query(&format!(
+        "CREATE TABLE IF NOT EXISTS {table} (
+            stamp TIMESTAMPTZ NOT NULL,
+            path TEXT NOT NULL,
+            vulnerable BOOLEAN NOT NULL,
+            report JSONB NOT NULL
+        )"
+    ))
+    .execute(&pool)
+    .await
+    .context("Create report_sql table")?;
+
+    // Enable TimescaleDB hypertable chunking, if the extension is installed.
+    // Ignore the error if it is not; a plain table still works fine.
+    let _ = sqlx::query(&format!(
+        "SELECT create_hypertable('{table}', 'stamp', if_not_exists => true)"
+    ))
+    .execute(&pool)
+    .await;
+
+    let mut tx = pool.begin().await.context("Begin report_sql transaction")?;
+
+    for entry in report.entries() {
+        sqlx::query(&format!(
+            "INSERT INTO {table} (stamp, path, vulnerable, report) VALUES ($1, $2, $3, $4::jsonb)"
+        ))
+        .bind(report.stamp())
+        .bind(entry.path.display().to_string())
+        .bind(entry.vulnerable)
+        .bind(&entry.json)
+        .execute(&mut *tx)
+        .await
+        .context("Insert audit_results row")?;
+    }
+
+    // Summary row, so overall run status can be charted alongside per-binary rows.
+    sqlx::query(&format!(
+        "INSERT INTO {table} (stamp, path, vulnerable, report) VALUES ($1, $2, $3, $4::jsonb)"
+    ))
+    .bind(report.stamp())
+    .bind("__summary__")
+    .bind(report.vulnerable())
+    .bind(json::Value::String(format!("{report}")))
+    .execute(&mut *tx)
+    .await
+    .context("Insert audit_results summary row")?;
+
+    tx.commit().await.context("Commit report_sql transaction")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_table_name_accepts_bare_identifiers() {
+        assert!(validate_table_name("audit_results").is_ok());
+        assert!(validate_table_name("_audit_results2").is_ok());
+    }
+
+    #[test]
+    fn validate_table_name_rejects_sql_metacharacters() {
+        assert!(validate_table_name("audit_results; DROP TABLE users;--").is_err());
+        assert!(validate_table_name("audit results").is_err());
+        assert!(validate_table_name("2audit").is_err());
+        assert!(validate_table_name("").is_err());
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,159 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use crate::{
+    advisory::Finding,
+    config::{Config, ConfigReportWebhook},
+    notify::Notifier as _,
+    report::Report,
+};
+use anyhow::{self as ah, Context as _};
+use serde::Serialize;
+use serde_json as json;
+
+#[derive(Serialize)]
+struct WebhookEntry<'a> {
+    path: String,
+    kind: String,
+    vulnerable: bool,
+    max_severity: String,
+    findings: &'a [Finding],
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    status: &'a str,
+    failed: bool,
+    vulnerable: bool,
+    vulnerable_count: usize,
+    total_count: usize,
+    messages: &'a [String],
+    entries: Vec<WebhookEntry<'a>>,
+}
+
+fn build_payload(report: &Report) -> WebhookPayload<'_> {
+    let status = if report.failed() {
+        "failed"
+    } else if report.vulnerable() {
+        "vulnerable"
+    } else {
+        "clean"
+    };
+    let entries = report
+        .entries()
+        .iter()
+        .map(|e| WebhookEntry {
+            path: e.path.display().to_string(),
+            kind: e.kind.to_string(),
+            vulnerable: e.vulnerable,
+            max_severity: e.max_severity.to_string(),
+            findings: &e.findings,
+        })
+        .collect();
+
+    WebhookPayload {
+        status,
+        failed: report.failed(),
+        vulnerable: report.vulnerable(),
+        vulnerable_count: report.entries().iter().filter(|e| e.vulnerable).count(),
+        total_count: report.entries().len(),
+        messages: report.messages(),
+        entries,
+    }
+}
+
+/// Render the webhook request body from `template`, substituting the shared
+/// `Report::render_template` tokens plus `{json}`, the compact structured
+/// payload (status/counts/per-entry advisory list) most chat/incident
+/// integrations expect.
+fn render_payload(template: &str, report: &Report, host: &str) -> ah::Result<String> {
+    let payload = json::to_string(&build_payload(report)).context("Serialize webhook payload")?;
+    Ok(report.render_template(template, host).replace("{json}", &payload))
+}
+
+/// HTTP webhook delivery of a `Report`, the other first-party
+/// [`crate::notify::Notifier`] implementation alongside
+/// [`crate::report::mail::MailNotifier`].
+pub struct WebhookNotifier(ConfigReportWebhook);
+
+impl WebhookNotifier {
+    pub fn new(rw: ConfigReportWebhook) -> Self {
+        Self(rw)
+    }
+}
+
+impl crate::notify::Notifier for WebhookNotifier {
+    async fn notify(&self, report: &Report) -> ah::Result<()> {
+        send_report_impl(&self.0, report).await
+    }
+}
+
+pub async fn send_report(config: &Config, report: &Report) -> ah::Result<()> {
+    let Some(rw) = config.report_webhook() else {
+        return Ok(());
+    };
+    if rw.disabled() {
+        println!("Webhook sending is disabled; not sending report webhook.");
+        return Ok(());
+    }
+    WebhookNotifier::new(rw.clone()).notify(report).await
+}
+
+async fn send_report_impl(rw: &ConfigReportWebhook, report: &Report) -> ah::Result<()> {
+    if rw.urls().is_empty() {
+        println!("No report_webhook URLs configured; not sending report webhook.");
+        return Ok(());
+    }
+
+    let host = hostname::get()
+        .map(|h| h.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let body = render_payload(rw.payload_template(), report, &host)
+        .context("Render webhook payload")?;
+
+    let client = reqwest::Client::new();
+    for url in rw.urls() {
+        client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(body.clone())
+            .send()
+            .await
+            .with_context(|| format!("POST webhook '{url}'"))?
+            .error_for_status()
+            .with_context(|| format!("Webhook '{url}' returned an error status"))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_payload_embeds_json_token() {
+        let mut report = Report::new();
+        report.add(crate::report::ReportEntry {
+            path: "/bin/foo".into(),
+            vulnerable: true,
+            ..Default::default()
+        });
+        let rendered = render_payload("{json}", &report, "myhost").unwrap();
+        let parsed: json::Value = json::from_str(&rendered).unwrap();
+        assert_eq!(parsed["status"], "vulnerable");
+        assert_eq!(parsed["vulnerable_count"], 1);
+        assert_eq!(parsed["entries"][0]["path"], "/bin/foo");
+    }
+
+    #[test]
+    fn render_payload_supports_custom_template() {
+        let report = Report::new();
+        let rendered =
+            render_payload(r#"{"text":"{status} on {host}"}"#, &report, "myhost").unwrap();
+        assert_eq!(rendered, r#"{"text":"OK on myhost"}"#);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -3,6 +3,8 @@
 // Copyright (C) 2026 Michael Büsch <m@bues.ch>
 
 use anyhow as ah;
+use std::time::Duration;
+use tokio::time::sleep;
 
 /// Notify ready-status to systemd.
 pub fn systemd_notify_ready() -> ah::Result<()> {
@@ -10,4 +12,35 @@ pub fn systemd_notify_ready() -> ah::Result<()> {
     Ok(())
 }
 
+/// Notify systemd that we are still alive (watchdog keep-alive ping).
+fn systemd_notify_watchdog() -> ah::Result<()> {
+    sd_notify::notify(false, &[sd_notify::NotifyState::Watchdog])?;
+    Ok(())
+}
+
+/// Spawn a background task that pings the systemd watchdog at half of
+/// `WatchdogSec`, read from the `WATCHDOG_USEC` environment variable systemd
+/// sets on the service. `WatchdogSec` supervision requires pings well inside
+/// that window; a single ping per (possibly 24h-long) audit interval either
+/// does nothing useful or gets the daemon killed, so this runs independently
+/// of the audit schedule. Returns `None` without spawning anything if the
+/// unit has no `WatchdogSec` configured.
+pub fn spawn_watchdog_pinger() -> Option<tokio::task::JoinHandle<()>> {
+    let period = sd_notify::watchdog_enabled(false)? / 2;
+    Some(tokio::spawn(async move {
+        loop {
+            sleep(period).await;
+            if let Err(e) = systemd_notify_watchdog() {
+                eprintln!("Failed to notify systemd watchdog: {e}");
+            }
+        }
+    }))
+}
+
+/// Update the freeform status text shown by `systemctl status`.
+pub fn systemd_notify_status(status: &str) -> ah::Result<()> {
+    sd_notify::notify(false, &[sd_notify::NotifyState::Status(status)])?;
+    Ok(())
+}
+
 // vim: ts=4 sw=4 expandtab
@@ -0,0 +1,68 @@
+// -*- coding: utf-8 -*-
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Copyright (C) 2026 Michael Büsch <m@bues.ch>
+
+use crate::config::Config;
+use anyhow::{self as ah, Context as _};
+use arc_swap::ArcSwap;
+use notify::Watcher as _;
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::mpsc;
+
+/// Watches the configuration file for modifications and atomically swaps
+/// `config` for the freshly parsed contents on each change.
+///
+/// A modification that fails to parse is logged and otherwise ignored, so a
+/// bad edit never brings down a running daemon; the previous configuration
+/// stays in effect until a valid one is written.
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+    _task: tokio::task::JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Spawn a background task that watches `path` and reloads `config` on change.
+    pub fn spawn(path: PathBuf, config: Arc<ArcSwap<Config>>) -> ah::Result<Self> {
+        let (tx, mut rx) = mpsc::channel(8);
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if event.kind.is_modify() || event.kind.is_create() {
+                    let _ = tx.blocking_send(());
+                }
+            }
+        })
+        .context("Create config file watcher")?;
+        watcher
+            .watch(&path, notify::RecursiveMode::NonRecursive)
+            .with_context(|| format!("Watch config file '{}'", path.display()))?;
+
+        let task = tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                match Config::load(&path).await {
+                    Ok(new_conf) => {
+                        println!(
+                            "Configuration file '{}' changed; reloaded.",
+                            path.display()
+                        );
+                        config.store(Arc::new(new_conf));
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "WARNING: Failed to reload configuration file '{}': {}; keeping previous configuration.",
+                            path.display(),
+                            e
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            _watcher: watcher,
+            _task: task,
+        })
+    }
+}
+
+// vim: ts=4 sw=4 expandtab